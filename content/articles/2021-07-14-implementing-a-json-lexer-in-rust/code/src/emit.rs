@@ -0,0 +1,170 @@
+use crate::parser::Value;
+use crate::types::JSONNumber;
+
+fn escape_string(string: &str, out: &mut String) {
+    out.push('"');
+
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{0008}' => out.push_str("\\b"),
+            '\u{000C}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+}
+
+fn emit_number(number: &JSONNumber) -> String {
+    match number {
+        JSONNumber::I64(int) => int.to_string(),
+        JSONNumber::U64(int) => int.to_string(),
+        JSONNumber::F64(float) => float.to_string(),
+    }
+}
+
+fn write_compact(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(boolean) => out.push_str(if *boolean { "true" } else { "false" }),
+        Value::Number(number) => out.push_str(&emit_number(number)),
+        Value::String(string) => escape_string(string, out),
+        Value::Array(items) => {
+            out.push('[');
+
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+
+                write_compact(item, out);
+            }
+
+            out.push(']');
+        }
+        Value::Object(members) => {
+            out.push('{');
+
+            for (index, (key, value)) in members.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+
+                escape_string(key, out);
+                out.push(':');
+                write_compact(value, out);
+            }
+
+            out.push('}');
+        }
+    }
+}
+
+fn push_indent(out: &mut String, indent: usize, depth: usize) {
+    for _ in 0..indent * depth {
+        out.push(' ');
+    }
+}
+
+fn write_pretty(value: &Value, indent: usize, depth: usize, out: &mut String) {
+    match value {
+        Value::Array(items) if !items.is_empty() => {
+            out.push_str("[\n");
+
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(",\n");
+                }
+
+                push_indent(out, indent, depth + 1);
+                write_pretty(item, indent, depth + 1, out);
+            }
+
+            out.push('\n');
+            push_indent(out, indent, depth);
+            out.push(']');
+        }
+        Value::Object(members) if !members.is_empty() => {
+            out.push_str("{\n");
+
+            for (index, (key, value)) in members.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(",\n");
+                }
+
+                push_indent(out, indent, depth + 1);
+                escape_string(key, out);
+                out.push_str(": ");
+                write_pretty(value, indent, depth + 1, out);
+            }
+
+            out.push('\n');
+            push_indent(out, indent, depth);
+            out.push('}');
+        }
+        value => write_compact(value, out),
+    }
+}
+
+pub fn to_string(value: &Value) -> String {
+    let mut out = String::new();
+    write_compact(value, &mut out);
+    out
+}
+
+pub fn to_string_pretty(value: &Value, indent: usize) -> String {
+    let mut out = String::new();
+    write_pretty(value, indent, 0, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::lexer::lex;
+    use crate::parser::parse;
+
+    fn value(source: &str) -> Value {
+        parse(lex(&source.to_string()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn compact_object() {
+        let document = value(r#"{ "foo" : "bar" , "baz" : [ 1 , true , null ] }"#);
+        assert_eq!(to_string(&document), r#"{"foo":"bar","baz":[1,true,null]}"#);
+    }
+
+    #[test]
+    fn integers_have_no_trailing_point() {
+        assert_eq!(to_string(&value("2")), "2");
+        assert_eq!(to_string(&value("-5")), "-5");
+    }
+
+    #[test]
+    fn strings_are_reescaped() {
+        let document = value(r#""he said \"hi\"\nbye""#);
+        assert_eq!(to_string(&document), r#""he said \"hi\"\nbye""#);
+    }
+
+    #[test]
+    fn empty_containers_stay_inline_when_pretty() {
+        assert_eq!(to_string_pretty(&value("[]"), 2), "[]");
+        assert_eq!(to_string_pretty(&value("{}"), 2), "{}");
+    }
+
+    #[test]
+    fn pretty_indents_nested_values() {
+        let document = value(r#"{"a":[1,2]}"#);
+        assert_eq!(
+            to_string_pretty(&document, 2),
+            "{\n  \"a\": [\n    1,\n    2\n  ]\n}"
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::parser::Value;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathSegment {
+    Child(String),
+    Wildcard,
+    RecursiveDescent,
+    Index(i64),
+    Slice(Option<i64>, Option<i64>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathError {
+    MissingRoot,
+    InvalidSyntax,
+    UnclosedBracket,
+}
+
+type Result<T> = std::result::Result<T, PathError>;
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn read_name(chars: &mut Peekable<Chars>) -> String {
+    let mut name = String::new();
+
+    while let Some(&c) = chars.peek() {
+        if is_name_char(c) {
+            name.push(c);
+            chars.next();
+        } else {
+            break;
+        }
+    }
+
+    name
+}
+
+fn parse_bracket(inner: &str) -> Result<PathSegment> {
+    let inner = inner.trim();
+
+    if inner == "*" {
+        return Ok(PathSegment::Wildcard);
+    }
+
+    if (inner.starts_with('\'') && inner.ends_with('\''))
+        || (inner.starts_with('"') && inner.ends_with('"'))
+    {
+        if inner.len() < 2 {
+            return Err(PathError::InvalidSyntax);
+        }
+
+        return Ok(PathSegment::Child(inner[1..inner.len() - 1].to_string()));
+    }
+
+    if let Some((start, end)) = inner.split_once(':') {
+        let bound = |part: &str| -> Result<Option<i64>> {
+            let part = part.trim();
+
+            if part.is_empty() {
+                Ok(None)
+            } else {
+                part.parse::<i64>().map(Some).map_err(|_| PathError::InvalidSyntax)
+            }
+        };
+
+        return Ok(PathSegment::Slice(bound(start)?, bound(end)?));
+    }
+
+    inner
+        .parse::<i64>()
+        .map(PathSegment::Index)
+        .map_err(|_| PathError::InvalidSyntax)
+}
+
+fn compile(path: &str) -> Result<Vec<PathSegment>> {
+    let mut chars = path.chars().peekable();
+
+    match chars.next() {
+        Some('$') => {}
+        _ => return Err(PathError::MissingRoot),
+    }
+
+    let mut segments = Vec::new();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            '.' => {
+                chars.next();
+
+                let recursive = chars.peek() == Some(&'.');
+
+                if recursive {
+                    chars.next();
+                    segments.push(PathSegment::RecursiveDescent);
+                }
+
+                match chars.peek() {
+                    Some('*') => {
+                        chars.next();
+                        segments.push(PathSegment::Wildcard);
+                    }
+                    Some('[') => {}
+                    Some(&c) if is_name_char(c) => {
+                        segments.push(PathSegment::Child(read_name(&mut chars)));
+                    }
+                    _ if recursive => {}
+                    _ => return Err(PathError::InvalidSyntax),
+                }
+            }
+            '[' => {
+                chars.next();
+
+                let mut inner = String::new();
+
+                while let Some(&c) = chars.peek() {
+                    if c == ']' {
+                        break;
+                    }
+
+                    inner.push(c);
+                    chars.next();
+                }
+
+                match chars.next() {
+                    Some(']') => {}
+                    _ => return Err(PathError::UnclosedBracket),
+                }
+
+                segments.push(parse_bracket(&inner)?);
+            }
+            _ => return Err(PathError::InvalidSyntax),
+        }
+    }
+
+    Ok(segments)
+}
+
+fn collect_descendants<'a>(node: &'a Value, out: &mut Vec<&'a Value>) {
+    out.push(node);
+
+    match node {
+        Value::Array(items) => items.iter().for_each(|item| collect_descendants(item, out)),
+        Value::Object(members) => members
+            .iter()
+            .for_each(|(_, value)| collect_descendants(value, out)),
+        _ => {}
+    }
+}
+
+fn normalize_index(index: i64, len: usize) -> Option<usize> {
+    let resolved = if index < 0 {
+        len as i64 + index
+    } else {
+        index
+    };
+
+    if resolved >= 0 && (resolved as usize) < len {
+        Some(resolved as usize)
+    } else {
+        None
+    }
+}
+
+fn normalize_bound(bound: Option<i64>, default: usize, len: usize) -> usize {
+    match bound {
+        None => default,
+        Some(value) if value < 0 => (len as i64 + value).max(0) as usize,
+        Some(value) => (value as usize).min(len),
+    }
+}
+
+pub fn select<'a>(value: &'a Value, path: &str) -> Result<Vec<&'a Value>> {
+    let segments = compile(path)?;
+    let mut current = vec![value];
+
+    for segment in &segments {
+        let mut next = Vec::new();
+
+        match segment {
+            PathSegment::RecursiveDescent => {
+                for node in &current {
+                    collect_descendants(node, &mut next);
+                }
+            }
+            PathSegment::Child(name) => {
+                for node in &current {
+                    if let Value::Object(members) = node {
+                        for (key, value) in members {
+                            if key == name {
+                                next.push(value);
+                            }
+                        }
+                    }
+                }
+            }
+            PathSegment::Wildcard => {
+                for node in &current {
+                    match node {
+                        Value::Object(members) => {
+                            members.iter().for_each(|(_, value)| next.push(value))
+                        }
+                        Value::Array(items) => items.iter().for_each(|item| next.push(item)),
+                        _ => {}
+                    }
+                }
+            }
+            PathSegment::Index(index) => {
+                for node in &current {
+                    if let Value::Array(items) = node {
+                        if let Some(resolved) = normalize_index(*index, items.len()) {
+                            next.push(&items[resolved]);
+                        }
+                    }
+                }
+            }
+            PathSegment::Slice(start, end) => {
+                for node in &current {
+                    if let Value::Array(items) = node {
+                        let from = normalize_bound(*start, 0, items.len());
+                        let to = normalize_bound(*end, items.len(), items.len());
+
+                        if from < to {
+                            items[from..to].iter().for_each(|item| next.push(item));
+                        }
+                    }
+                }
+            }
+        }
+
+        current = next;
+    }
+
+    Ok(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::lexer::lex;
+    use crate::parser::parse;
+    use crate::types::JSONNumber;
+
+    fn document() -> Value {
+        let source = r#"
+            {
+                "store": {
+                    "book": [
+                        {"author": "nigel rees", "price": 8.95},
+                        {"author": "evelyn waugh", "price": 12.99}
+                    ],
+                    "bicycle": {"color": "red", "price": 19.95}
+                }
+            }
+        "#;
+
+        parse(lex(&source.to_string()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn root_returns_document() {
+        let value = document();
+        assert_eq!(select(&value, "$"), Ok(vec![&value]));
+    }
+
+    #[test]
+    fn child_chain() {
+        let value = document();
+        let colors = select(&value, "$.store.bicycle.color").unwrap();
+        assert_eq!(colors, vec![&Value::String("red".to_string())]);
+    }
+
+    #[test]
+    fn bracket_child() {
+        let value = document();
+        let colors = select(&value, "$['store']['bicycle']['color']").unwrap();
+        assert_eq!(colors, vec![&Value::String("red".to_string())]);
+    }
+
+    #[test]
+    fn wildcard_over_array() {
+        let value = document();
+        let authors = select(&value, "$.store.book[*].author").unwrap();
+        assert_eq!(
+            authors,
+            vec![
+                &Value::String("nigel rees".to_string()),
+                &Value::String("evelyn waugh".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn recursive_descent() {
+        let value = document();
+        let prices = select(&value, "$..price").unwrap();
+        assert_eq!(
+            prices,
+            vec![
+                &Value::Number(JSONNumber::F64(8.95)),
+                &Value::Number(JSONNumber::F64(12.99)),
+                &Value::Number(JSONNumber::F64(19.95)),
+            ]
+        );
+    }
+
+    #[test]
+    fn array_index() {
+        let value = document();
+        let first = select(&value, "$.store.book[0].author").unwrap();
+        assert_eq!(first, vec![&Value::String("nigel rees".to_string())]);
+    }
+
+    #[test]
+    fn array_slice() {
+        let value = document();
+        let sliced = select(&value, "$.store.book[0:1]").unwrap();
+        assert_eq!(sliced.len(), 1);
+    }
+
+    #[test]
+    fn missing_root_is_rejected() {
+        let value = document();
+        assert_eq!(select(&value, "store"), Err(PathError::MissingRoot));
+    }
+}
@@ -11,13 +11,23 @@ pub enum Error {
     UnclosedString,
     InvalidNumberFormat,
     InvalidExponentFormat,
+    InvalidEscape,
+    InvalidUnicodeEscape,
+    LoneSurrogate,
 }
 
 type Result<T> = std::result::Result<T, Error>;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
 #[derive(Debug)]
 pub struct Lexer<I: Iterator<Item = char>> {
     source: Peekable<I>,
+    offset: usize,
 }
 
 impl<I> From<I> for Lexer<I>
@@ -27,6 +37,7 @@ where
     fn from(source: I) -> Self {
         Lexer {
             source: source.peekable(),
+            offset: 0,
         }
     }
 }
@@ -35,111 +46,156 @@ impl<I> Iterator for Lexer<I>
 where
     I: Iterator<Item = char>,
 {
-    type Item = Result<Token>;
+    type Item = std::result::Result<(Token, Span), (Error, Span)>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.source.next() {
-            Some(c) => match c {
-                '[' => Some(Ok(Token::Bracket(ParenthesisType::Open))),
-                ']' => Some(Ok(Token::Bracket(ParenthesisType::Close))),
-                '{' => Some(Ok(Token::CurlyBracket(ParenthesisType::Open))),
-                '}' => Some(Ok(Token::CurlyBracket(ParenthesisType::Close))),
-                ',' => Some(Ok(Token::ElementDelimiter)),
-                ':' => Some(Ok(Token::KeyDelimiter)),
-                '"' => {
-                    let string = self.read_while(|next| next != '"');
-
-                    match self.source.next() {
-                        Some('"') => Some(Ok(Token::String(string))),
-                        _ => Some(Err(Error::UnclosedString)),
-                    }
-                }
-                c if c.is_ascii_whitespace() => self.next(),
-                c if c.is_ascii_digit() || c == '-' || c == '+' => {
-                    {
-                        let err = match c {
-                            '0' => match self.source.peek() {
-                                Some(n) if n.is_ascii_digit() => Some(Error::InvalidNumberFormat),
-                                _ => None,
-                            },
-                            '+' => Some(Error::InvalidNumberFormat),
+        loop {
+            let start = self.offset;
+
+            let c = self.bump()?;
+
+            if c.is_ascii_whitespace() {
+                continue;
+            }
+
+            let result = self.scan(c);
+            let span = Span {
+                start,
+                end: self.offset,
+            };
+
+            return Some(match result {
+                Ok(token) => Ok((token, span)),
+                Err(error) => Err((error, span)),
+            });
+        }
+    }
+}
+
+impl<I> Lexer<I>
+where
+    I: Iterator<Item = char>,
+{
+    fn scan(&mut self, c: char) -> Result<Token> {
+        match c {
+            '[' => Ok(Token::Bracket(ParenthesisType::Open)),
+            ']' => Ok(Token::Bracket(ParenthesisType::Close)),
+            '{' => Ok(Token::CurlyBracket(ParenthesisType::Open)),
+            '}' => Ok(Token::CurlyBracket(ParenthesisType::Close)),
+            ',' => Ok(Token::ElementDelimiter),
+            ':' => Ok(Token::KeyDelimiter),
+            '"' => self.read_string().map(Token::String),
+            c if c.is_ascii_digit() || c == '-' || c == '+' => {
+                {
+                    let err = match c {
+                        '0' => match self.source.peek() {
+                            Some(n) if n.is_ascii_digit() => Some(Error::InvalidNumberFormat),
                             _ => None,
-                        };
+                        },
+                        '+' => Some(Error::InvalidNumberFormat),
+                        _ => None,
+                    };
 
-                        if let Some(e) = err {
-                            self.read_while(|next| match next {
-                                ',' | ']' | '}' => false,
-                                _ => true,
-                            });
+                    if let Some(e) = err {
+                        self.read_while(|next| match next {
+                            ',' | ']' | '}' => false,
+                            _ => true,
+                        });
 
-                            return Some(Err(e));
-                        }
+                        return Err(e);
                     }
+                }
 
-                    let mantissa = {
-                        let mut rest = self.read_while(|next| next.is_ascii_digit() || next == '.');
-                        rest.insert(0, c);
-                        rest
-                    };
+                let negative = c == '-';
 
-                    let exponent: Option<String> = match self.source.peek() {
-                        Some('e') | Some('E') => {
-                            self.source.next();
+                let mantissa = {
+                    let mut rest = self.read_while(|next| next.is_ascii_digit() || next == '.');
+                    rest.insert(0, c);
+                    rest
+                };
 
-                            let exp = self.read_while(|next| {
-                                next.is_ascii_digit() || next == '+' || next == '-'
-                            });
+                let exponent: Option<String> = match self.source.peek() {
+                    Some('e') | Some('E') => {
+                        self.bump();
 
-                            if exp == "+" || exp == "-" || exp.is_empty() {
-                                return Some(Err(Error::InvalidExponentFormat));
-                            }
+                        let exp = self
+                            .read_while(|next| next.is_ascii_digit() || next == '+' || next == '-');
 
-                            Some(exp)
+                        if exp == "+" || exp == "-" || exp.is_empty() {
+                            return Err(Error::InvalidExponentFormat);
                         }
-                        _ => None,
-                    };
 
-                    let parsed_mantissa = match mantissa.parse::<f64>() {
-                        Ok(num) => num,
-                        Err(_) => return Some(Err(Error::InvalidNumberFormat)),
-                    };
+                        Some(exp)
+                    }
+                    _ => None,
+                };
+
+                let parsed_exponent = match exponent.map(|e| e.parse::<i16>()) {
+                    Some(Err(_)) => return Err(Error::InvalidNumberFormat),
+                    Some(Ok(exponent)) => Some(exponent),
+                    None => None,
+                };
 
-                    let parsed_exponent = match exponent.map(|e| e.parse::<i16>()) {
-                        Some(Err(_)) => return Some(Err(Error::InvalidNumberFormat)),
-                        Some(Ok(exponent)) => Some(exponent),
-                        None => None,
+                let number = if mantissa.contains('.') || parsed_exponent.is_some() {
+                    let base = match mantissa.parse::<f64>() {
+                        Ok(num) => num,
+                        Err(_) => return Err(Error::InvalidNumberFormat),
                     };
 
-                    Some(Ok(Token::Number(JSONNumber::new(
-                        parsed_mantissa,
-                        parsed_exponent,
-                    ))))
-                }
-                c if c.is_ascii_alphabetic() => {
-                    let keyword = {
-                        let mut rest = self.read_while(|next| next.is_ascii_alphabetic());
-                        rest.insert(0, c);
-                        rest
+                    let value = match parsed_exponent {
+                        Some(exp) => base * 10f64.powi(exp as i32),
+                        None => base,
                     };
 
-                    match keyword.as_str() {
-                        "true" => Some(Ok(Token::Boolean(true))),
-                        "false" => Some(Ok(Token::Boolean(false))),
-                        "null" => Some(Ok(Token::Null)),
-                        _ => Some(Err(Error::UnkownKeyword)),
+                    JSONNumber::F64(value)
+                } else if negative {
+                    match mantissa.parse::<i64>() {
+                        Ok(int) => JSONNumber::I64(int),
+                        Err(_) => match mantissa.parse::<f64>() {
+                            Ok(num) => JSONNumber::F64(num),
+                            Err(_) => return Err(Error::InvalidNumberFormat),
+                        },
+                    }
+                } else {
+                    match mantissa.parse::<u64>() {
+                        Ok(int) => JSONNumber::U64(int),
+                        Err(_) => match mantissa.parse::<f64>() {
+                            Ok(num) => JSONNumber::F64(num),
+                            Err(_) => return Err(Error::InvalidNumberFormat),
+                        },
                     }
+                };
+
+                Ok(Token::Number(number))
+            }
+            c if c.is_ascii_alphabetic() => {
+                let keyword = {
+                    let mut rest = self.read_while(|next| next.is_ascii_alphabetic());
+                    rest.insert(0, c);
+                    rest
+                };
+
+                match keyword.as_str() {
+                    "true" => Ok(Token::Boolean(true)),
+                    "false" => Ok(Token::Boolean(false)),
+                    "null" => Ok(Token::Null),
+                    _ => Err(Error::UnkownKeyword),
                 }
-                _ => Some(Err(Error::UnknownChar)),
-            },
-            None => None,
+            }
+            _ => Err(Error::UnknownChar),
         }
     }
-}
 
-impl<I> Lexer<I>
-where
-    I: Iterator<Item = char>,
-{
+    fn bump(&mut self) -> Option<char> {
+        let next = self.source.next();
+
+        if next.is_some() {
+            self.offset += 1;
+        }
+
+        next
+    }
+
     fn read_while<T>(&mut self, predicate: T) -> String
     where
         T: Fn(char) -> bool,
@@ -149,7 +205,7 @@ where
         while let Some(next) = self.source.peek() {
             match *next {
                 next if predicate(next) => {
-                    res.push(self.source.next().unwrap());
+                    res.push(self.bump().unwrap());
                 }
                 _ => break,
             }
@@ -157,12 +213,79 @@ where
 
         res
     }
+
+    fn read_string(&mut self) -> Result<String> {
+        let mut res = String::new();
+
+        loop {
+            match self.bump() {
+                None => return Err(Error::UnclosedString),
+                Some('"') => return Ok(res),
+                Some('\\') => match self.bump() {
+                    None => return Err(Error::UnclosedString),
+                    Some('"') => res.push('"'),
+                    Some('\\') => res.push('\\'),
+                    Some('/') => res.push('/'),
+                    Some('b') => res.push('\u{0008}'),
+                    Some('f') => res.push('\u{000C}'),
+                    Some('n') => res.push('\n'),
+                    Some('r') => res.push('\r'),
+                    Some('t') => res.push('\t'),
+                    Some('u') => res.push(self.read_unicode_escape()?),
+                    Some(_) => return Err(Error::InvalidEscape),
+                },
+                Some(c) => res.push(c),
+            }
+        }
+    }
+
+    fn read_unicode_escape(&mut self) -> Result<char> {
+        let code = self.read_hex4()?;
+
+        match code {
+            0xD800..=0xDBFF => {
+                if self.bump() != Some('\\') || self.bump() != Some('u') {
+                    return Err(Error::LoneSurrogate);
+                }
+
+                let low = self.read_hex4()?;
+
+                if !(0xDC00..=0xDFFF).contains(&low) {
+                    return Err(Error::LoneSurrogate);
+                }
+
+                let scalar = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                char::from_u32(scalar).ok_or(Error::InvalidUnicodeEscape)
+            }
+            0xDC00..=0xDFFF => Err(Error::LoneSurrogate),
+            _ => char::from_u32(code).ok_or(Error::InvalidUnicodeEscape),
+        }
+    }
+
+    fn read_hex4(&mut self) -> Result<u32> {
+        let mut value = 0u32;
+
+        for _ in 0..4 {
+            match self.bump().and_then(|c| c.to_digit(16)) {
+                Some(digit) => value = value * 16 + digit,
+                None => return Err(Error::InvalidUnicodeEscape),
+            }
+        }
+
+        Ok(value)
+    }
 }
 
-pub fn lex(source: &String) -> Result<Vec<Token>> {
+pub fn lex_spanned(source: &String) -> std::result::Result<Vec<(Token, Span)>, (Error, Span)> {
     Lexer::from(source.chars()).collect()
 }
 
+pub fn lex(source: &String) -> Result<Vec<Token>> {
+    lex_spanned(source)
+        .map(|tokens| tokens.into_iter().map(|(token, _)| token).collect())
+        .map_err(|(error, _)| error)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -315,91 +438,107 @@ mod tests {
         lexer_tests! {
             number_zero: (
                 "0",
-                Ok(vec![Token::Number(JSONNumber::new(0.0, None))])
+                Ok(vec![Token::Number(JSONNumber::U64(0))])
             ),
             number_multiple: (
                 "0 1 2",
                 Ok(vec![
-                    Token::Number(JSONNumber::new(0.0, None)),
-                    Token::Number(JSONNumber::new(1.0, None)),
-                    Token::Number(JSONNumber::new(2.0, None)),
+                    Token::Number(JSONNumber::U64(0)),
+                    Token::Number(JSONNumber::U64(1)),
+                    Token::Number(JSONNumber::U64(2)),
                 ])
             ),
             number_long: (
                 "1000000",
-                Ok(vec![Token::Number(JSONNumber::new(1000000.0, None))])
+                Ok(vec![Token::Number(JSONNumber::U64(1000000))])
             ),
             number_no_exponent: (
                 "4",
-                Ok(vec![Token::Number(JSONNumber::new(4.0, None))])
+                Ok(vec![Token::Number(JSONNumber::U64(4))])
+            ),
+            number_negative: (
+                "-4",
+                Ok(vec![Token::Number(JSONNumber::I64(-4))])
+            ),
+            number_u64_max: (
+                "18446744073709551615",
+                Ok(vec![Token::Number(JSONNumber::U64(18446744073709551615))])
+            ),
+            number_preserves_integer_precision: (
+                "9007199254740993",
+                Ok(vec![Token::Number(JSONNumber::U64(9007199254740993))])
+            ),
+            number_integer_overflow_falls_back_to_float: (
+                "99999999999999999999999999999",
+                Ok(vec![Token::Number(JSONNumber::F64(99999999999999999999999999999.0))])
             ),
             number_unsigned_exponent: (
                 "4E2",
-                Ok(vec![Token::Number(JSONNumber::new(4.0, Some(2)))])
+                Ok(vec![Token::Number(JSONNumber::F64(4.0 * 10f64.powi(2)))])
             ),
             number_unsigned_long_exponent: (
                 "4E200",
-                Ok(vec![Token::Number(JSONNumber::new(4.0, Some(200)))])
+                Ok(vec![Token::Number(JSONNumber::F64(4.0 * 10f64.powi(200)))])
             ),
             number_unsigned_padded_exponent: (
                 "4E000002",
-                Ok(vec![Token::Number(JSONNumber::new(4.0, Some(2)))])
+                Ok(vec![Token::Number(JSONNumber::F64(4.0 * 10f64.powi(2)))])
             ),
             number_pos_signed_padded_exponent: (
                 "4E+000002",
-                Ok(vec![Token::Number(JSONNumber::new(4.0, Some(2)))])
+                Ok(vec![Token::Number(JSONNumber::F64(4.0 * 10f64.powi(2)))])
             ),
             number_signed_exponent: (
                 "4E-2",
-                Ok(vec![Token::Number(JSONNumber::new(4.0, Some(-2)))])
+                Ok(vec![Token::Number(JSONNumber::F64(4.0 * 10f64.powi(-2)))])
             ),
             number_signed_padded_exponent: (
                 "4E-000002",
-                Ok(vec![Token::Number(JSONNumber::new(4.0, Some(-2)))])
+                Ok(vec![Token::Number(JSONNumber::F64(4.0 * 10f64.powi(-2)))])
             ),
             float: (
                 "14.0",
-                Ok(vec![Token::Number(JSONNumber::new(14.0, None))])
+                Ok(vec![Token::Number(JSONNumber::F64(14.0))])
             ),
             float_multiple: (
                 "0.1 12.5 2.12",
                 Ok(vec![
-                    Token::Number(JSONNumber::new(0.1, None)),
-                    Token::Number(JSONNumber::new(12.5, None)),
-                    Token::Number(JSONNumber::new(2.12, None)),
+                    Token::Number(JSONNumber::F64(0.1)),
+                    Token::Number(JSONNumber::F64(12.5)),
+                    Token::Number(JSONNumber::F64(2.12)),
                 ])
             ),
             float_multiple_exp: (
                 "0.1E1 12.5E-0002 2.12E213",
                 Ok(vec![
-                    Token::Number(JSONNumber::new(0.1, Some(1))),
-                    Token::Number(JSONNumber::new(12.5, Some(-0002))),
-                    Token::Number(JSONNumber::new(2.12, Some(213))),
+                    Token::Number(JSONNumber::F64(0.1 * 10f64.powi(1))),
+                    Token::Number(JSONNumber::F64(12.5 * 10f64.powi(-2))),
+                    Token::Number(JSONNumber::F64(2.12 * 10f64.powi(213))),
                 ])
             ),
             float_long: (
                 "10000000000000000.0",
-                Ok(vec![Token::Number(JSONNumber::new(10000000000000000.0, None))])
+                Ok(vec![Token::Number(JSONNumber::F64(10000000000000000.0))])
             ),
             float_complex: (
                 "214.12498",
-                Ok(vec![Token::Number(JSONNumber::new(214.12498, None))])
+                Ok(vec![Token::Number(JSONNumber::F64(214.12498))])
             ),
             float_signed_complex: (
                 "-214.12498",
-                Ok(vec![Token::Number(JSONNumber::new(-214.12498, None))])
+                Ok(vec![Token::Number(JSONNumber::F64(-214.12498))])
             ),
             float_signed_exp: (
                 "-214.12498E+001",
-                Ok(vec![Token::Number(JSONNumber::new(-214.12498, Some(1)))])
+                Ok(vec![Token::Number(JSONNumber::F64(-214.12498 * 10f64.powi(1)))])
             ),
             float_signed_negative_exp: (
                 "-214.12498E-200",
-                Ok(vec![Token::Number(JSONNumber::new(-214.12498, Some(-200)))])
+                Ok(vec![Token::Number(JSONNumber::F64(-214.12498 * 10f64.powi(-200)))])
             ),
             float_unsigned_exp: (
                 "2.0E2",
-                Ok(vec![Token::Number(JSONNumber::new(2.0, Some(2)))])
+                Ok(vec![Token::Number(JSONNumber::F64(2.0 * 10f64.powi(2)))])
             ),
             invalid_float_many_decimal_points: (
                 "20.0.0.0",
@@ -530,6 +669,38 @@ mod tests {
                     Token::String(" baz".to_string())
                 ])
             ),
+            string_escaped_quote: (
+                r#""he said \"hi\"""#,
+                Ok(vec![Token::String(r#"he said "hi""#.to_string())])
+            ),
+            string_escaped_backslash: (
+                r#""a\\b""#,
+                Ok(vec![Token::String("a\\b".to_string())])
+            ),
+            string_escaped_control: (
+                r#""line\nbreak\ttab""#,
+                Ok(vec![Token::String("line\nbreak\ttab".to_string())])
+            ),
+            string_unicode_escape: (
+                r#""\u0041\u00e9""#,
+                Ok(vec![Token::String("A\u{00e9}".to_string())])
+            ),
+            string_surrogate_pair: (
+                r#""\uD83D\uDE00""#,
+                Ok(vec![Token::String("\u{1F600}".to_string())])
+            ),
+            string_invalid_escape: (
+                r#""\x""#,
+                Err(Error::InvalidEscape)
+            ),
+            string_invalid_unicode_escape: (
+                r#""\u00zz""#,
+                Err(Error::InvalidUnicodeEscape)
+            ),
+            string_lone_surrogate: (
+                r#""\uD83D""#,
+                Err(Error::LoneSurrogate)
+            ),
         }
     }
 
@@ -558,7 +729,7 @@ mod tests {
                     Token::String("baz".to_string()),
                     Token::KeyDelimiter,
                     Token::Bracket(ParenthesisType::Open),
-                    Token::Number(JSONNumber::new(2.0, None)),
+                    Token::Number(JSONNumber::F64(2.0)),
                     Token::ElementDelimiter,
                     Token::Boolean(true),
                     Token::ElementDelimiter,
@@ -583,4 +754,28 @@ mod tests {
             ),
         }
     }
+
+    mod spans {
+        use super::*;
+
+        #[test]
+        fn tokens_carry_their_span() {
+            assert_eq!(
+                lex_spanned(&"[ 12 ]".to_string()),
+                Ok(vec![
+                    (Token::Bracket(ParenthesisType::Open), Span { start: 0, end: 1 }),
+                    (Token::Number(JSONNumber::U64(12)), Span { start: 2, end: 4 }),
+                    (Token::Bracket(ParenthesisType::Close), Span { start: 5, end: 6 }),
+                ])
+            );
+        }
+
+        #[test]
+        fn errors_report_the_offending_span() {
+            assert_eq!(
+                lex_spanned(&"  +2".to_string()),
+                Err((Error::InvalidNumberFormat, Span { start: 2, end: 4 }))
+            );
+        }
+    }
 }
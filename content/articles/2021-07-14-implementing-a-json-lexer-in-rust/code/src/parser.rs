@@ -0,0 +1,211 @@
+use std::iter::Peekable;
+use std::vec::IntoIter;
+
+use crate::tokens::*;
+use crate::types::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(JSONNumber),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    UnexpectedEOF,
+    UnexpectedToken,
+    TrailingTokens,
+    ExpectedKey,
+}
+
+type Result<T> = std::result::Result<T, ParseError>;
+
+#[derive(Debug)]
+struct Parser {
+    tokens: Peekable<IntoIter<Token>>,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Parser {
+            tokens: tokens.into_iter().peekable(),
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        match self.tokens.next() {
+            None => Err(ParseError::UnexpectedEOF),
+            Some(token) => match token {
+                Token::Null => Ok(Value::Null),
+                Token::Boolean(boolean) => Ok(Value::Bool(boolean)),
+                Token::Number(number) => Ok(Value::Number(number)),
+                Token::String(string) => Ok(Value::String(string)),
+                Token::Bracket(ParenthesisType::Open) => self.array(),
+                Token::CurlyBracket(ParenthesisType::Open) => self.object(),
+                _ => Err(ParseError::UnexpectedToken),
+            },
+        }
+    }
+
+    fn array(&mut self) -> Result<Value> {
+        let mut items = Vec::new();
+
+        if let Some(Token::Bracket(ParenthesisType::Close)) = self.tokens.peek() {
+            self.tokens.next();
+            return Ok(Value::Array(items));
+        }
+
+        loop {
+            items.push(self.value()?);
+
+            match self.tokens.next() {
+                Some(Token::ElementDelimiter) => continue,
+                Some(Token::Bracket(ParenthesisType::Close)) => break,
+                Some(_) => return Err(ParseError::UnexpectedToken),
+                None => return Err(ParseError::UnexpectedEOF),
+            }
+        }
+
+        Ok(Value::Array(items))
+    }
+
+    fn object(&mut self) -> Result<Value> {
+        let mut members = Vec::new();
+
+        if let Some(Token::CurlyBracket(ParenthesisType::Close)) = self.tokens.peek() {
+            self.tokens.next();
+            return Ok(Value::Object(members));
+        }
+
+        loop {
+            let key = match self.tokens.next() {
+                Some(Token::String(string)) => string,
+                Some(_) => return Err(ParseError::ExpectedKey),
+                None => return Err(ParseError::UnexpectedEOF),
+            };
+
+            match self.tokens.next() {
+                Some(Token::KeyDelimiter) => {}
+                Some(_) => return Err(ParseError::UnexpectedToken),
+                None => return Err(ParseError::UnexpectedEOF),
+            }
+
+            members.push((key, self.value()?));
+
+            match self.tokens.next() {
+                Some(Token::ElementDelimiter) => continue,
+                Some(Token::CurlyBracket(ParenthesisType::Close)) => break,
+                Some(_) => return Err(ParseError::UnexpectedToken),
+                None => return Err(ParseError::UnexpectedEOF),
+            }
+        }
+
+        Ok(Value::Object(members))
+    }
+}
+
+pub fn parse(tokens: Vec<Token>) -> Result<Value> {
+    let mut parser = Parser::new(tokens);
+    let value = parser.value()?;
+
+    match parser.tokens.next() {
+        None => Ok(value),
+        Some(_) => Err(ParseError::TrailingTokens),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::lexer::lex;
+
+    fn parse_str(source: &str) -> Result<Value> {
+        parse(lex(&source.to_string()).unwrap())
+    }
+
+    macro_rules! parser_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (input, expected) = $value;
+                    assert_eq!(parse_str(input), expected);
+                }
+            )*
+        }
+    }
+
+    mod scalars {
+        use super::*;
+
+        parser_tests! {
+            null: ("null", Ok(Value::Null)),
+            boolean_true: ("true", Ok(Value::Bool(true))),
+            boolean_false: ("false", Ok(Value::Bool(false))),
+            number: ("42", Ok(Value::Number(JSONNumber::U64(42)))),
+            string: (r#""foo""#, Ok(Value::String("foo".to_string()))),
+        }
+    }
+
+    mod arrays {
+        use super::*;
+
+        parser_tests! {
+            empty: ("[]", Ok(Value::Array(vec![]))),
+            scalars: (
+                "[1, true, null]",
+                Ok(Value::Array(vec![
+                    Value::Number(JSONNumber::U64(1)),
+                    Value::Bool(true),
+                    Value::Null,
+                ]))
+            ),
+            nested: (
+                "[[1], []]",
+                Ok(Value::Array(vec![
+                    Value::Array(vec![Value::Number(JSONNumber::U64(1))]),
+                    Value::Array(vec![]),
+                ]))
+            ),
+            trailing_comma: ("[1,]", Err(ParseError::UnexpectedToken)),
+        }
+    }
+
+    mod objects {
+        use super::*;
+
+        parser_tests! {
+            empty: ("{}", Ok(Value::Object(vec![]))),
+            single: (
+                r#"{"foo": "bar"}"#,
+                Ok(Value::Object(vec![
+                    ("foo".to_string(), Value::String("bar".to_string())),
+                ]))
+            ),
+            nested: (
+                r#"{"a": {"b": 1}}"#,
+                Ok(Value::Object(vec![
+                    ("a".to_string(), Value::Object(vec![
+                        ("b".to_string(), Value::Number(JSONNumber::U64(1))),
+                    ])),
+                ]))
+            ),
+            missing_key: ("{1: 2}", Err(ParseError::ExpectedKey)),
+            missing_colon: (r#"{"a" 1}"#, Err(ParseError::UnexpectedToken)),
+        }
+    }
+
+    mod whole {
+        use super::*;
+
+        parser_tests! {
+            trailing_tokens: ("null null", Err(ParseError::TrailingTokens)),
+            empty_input: ("", Err(ParseError::UnexpectedEOF)),
+        }
+    }
+}
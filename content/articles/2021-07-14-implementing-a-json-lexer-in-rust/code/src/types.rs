@@ -2,13 +2,8 @@ pub type JSONBoolean = bool;
 pub type JSONString = String;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub struct JSONNumber {
-    mantissa: f64,
-    exponent: Option<i16>,
-}
-
-impl JSONNumber {
-    pub fn new(mantissa: f64, exponent: Option<i16>) -> Self {
-        Self { mantissa, exponent }
-    }
+pub enum JSONNumber {
+    I64(i64),
+    U64(u64),
+    F64(f64),
 }